@@ -0,0 +1,598 @@
+//! Types shared between the query and schema languages.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde_json")]
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+#[cfg(feature = "serde_json")]
+use serde::Deserialize;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+pub trait Text<'a>: 'a {
+    type Value: 'a;
+}
+
+/// A GraphQL numeric literal.
+///
+/// graphql-js keeps integer literals as strings to avoid precision loss —
+/// an `IntValue` isn't bounded to `i64`'s range — so this stores the raw
+/// digits rather than narrowing to `i64`/`i32` up front. [`Number::as_i64`]
+/// is a fallible convenience accessor: it returns `None` for a literal
+/// outside `i64`'s range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number(String);
+
+impl Number {
+    pub fn as_i64(&self) -> Option<i64> {
+        self.0.parse().ok()
+    }
+}
+
+impl From<i64> for Number {
+    fn from(i: i64) -> Self {
+        Number(i.to_string())
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Type references, as used on variable definitions and input values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type<'a, T: Text<'a>> {
+    NamedType(T::Value),
+    ListType(Box<Type<'a, T>>),
+    NonNullType(Box<Type<'a, T>>),
+}
+
+impl<'a, T: Text<'a>> Serialize for Type<'a, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Type::NamedType(value) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "NamedType")?;
+                map.serialize_entry(
+                    "name",
+                    &NameKind {
+                        value: value.as_ref(),
+                    },
+                )?;
+                map.end()
+            }
+            Type::ListType(inner) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "ListType")?;
+                map.serialize_entry("type", inner)?;
+                map.end()
+            }
+            Type::NonNullType(inner) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "NonNullType")?;
+                map.serialize_entry("type", inner)?;
+                map.end()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<'de, T> Deserialize<'de> for Type<'static, T>
+where
+    T: Text<'static>,
+    T::Value: From<String>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TypeVisitor<T>(PhantomData<T>);
+
+        impl<'de, T> Visitor<'de> for TypeVisitor<T>
+        where
+            T: Text<'static>,
+            T::Value: From<String>,
+        {
+            type Value = Type<'static, T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    f,
+                    "a graphql-js Type node ({{ kind: \"NamedType\"|\"ListType\"|\"NonNullType\", .. }})"
+                )
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                #[derive(Deserialize)]
+                #[serde(tag = "kind")]
+                enum Raw<T: Text<'static>>
+                where
+                    T::Value: From<String>,
+                {
+                    NamedType { name: NameNodeHelper },
+                    ListType { r#type: Type<'static, T> },
+                    NonNullType { r#type: Type<'static, T> },
+                }
+
+                let raw = Raw::<T>::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(match raw {
+                    Raw::NamedType { name } => Type::NamedType(name.value.into()),
+                    Raw::ListType { r#type } => Type::ListType(Box::new(r#type)),
+                    Raw::NonNullType { r#type } => Type::NonNullType(Box::new(r#type)),
+                })
+            }
+        }
+
+        deserializer.deserialize_map(TypeVisitor(PhantomData))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a, T: Text<'a>> {
+    Variable(T::Value),
+    Int(Number),
+    Float(f64),
+    /// A string literal. `block` is `true` for the triple-quoted syntax
+    /// (`"""like this"""`), mirroring graphql-js's `StringValue.block`
+    /// flag so printers can round-trip the original style.
+    String(String, bool),
+    Boolean(bool),
+    Null,
+    Enum(T::Value),
+    List(Vec<Value<'a, T>>),
+    Object(BTreeMap<T::Value, Value<'a, T>>),
+}
+
+impl<'a, T: Text<'a>> Serialize for Value<'a, T>
+where
+    T::Value: AsRef<str>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Value::Variable(name) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "Variable")?;
+                map.serialize_entry(
+                    "name",
+                    &NameKind {
+                        value: name.as_ref(),
+                    },
+                )?;
+                map.end()
+            }
+            Value::Int(number) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "IntValue")?;
+                map.serialize_entry("value", &number.to_string())?;
+                map.end()
+            }
+            Value::Float(number) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "FloatValue")?;
+                map.serialize_entry("value", &number.to_string())?;
+                map.end()
+            }
+            Value::String(value, block) => {
+                let mut map = serializer.serialize_map(Some(3))?;
+                map.serialize_entry("kind", "StringValue")?;
+                map.serialize_entry("value", value)?;
+                map.serialize_entry("block", block)?;
+                map.end()
+            }
+            Value::Boolean(value) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "BooleanValue")?;
+                map.serialize_entry("value", value)?;
+                map.end()
+            }
+            Value::Null => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("kind", "NullValue")?;
+                map.end()
+            }
+            Value::Enum(value) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "EnumValue")?;
+                map.serialize_entry("value", value.as_ref())?;
+                map.end()
+            }
+            Value::List(values) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "ListValue")?;
+                map.serialize_entry("values", values)?;
+                map.end()
+            }
+            Value::Object(fields) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("kind", "ObjectValue")?;
+                let fields: Vec<_> = fields
+                    .iter()
+                    .map(|(name, value)| NamedValueNode {
+                        kind: "ObjectField",
+                        name: name.as_ref(),
+                        value,
+                    })
+                    .collect();
+                map.serialize_entry("fields", &fields)?;
+                map.end()
+            }
+        }
+    }
+}
+
+/// Serializes a `(name, value)` pair as the `{ kind, name: <Name>, value }`
+/// shape graphql-js uses for both `Argument` and `ObjectField` nodes.
+struct NamedValueNode<'a, 'b, T: Text<'b>> {
+    kind: &'static str,
+    name: &'a str,
+    value: &'a Value<'b, T>,
+}
+
+impl<'a, 'b, T: Text<'b>> Serialize for NamedValueNode<'a, 'b, T>
+where
+    T::Value: AsRef<str>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("kind", self.kind)?;
+        map.serialize_entry("name", &NameKind { value: self.name })?;
+        map.serialize_entry("value", self.value)?;
+        map.end()
+    }
+}
+
+pub fn serialize_arguments<'a, T, S>(
+    args: &'a Vec<(T::Value, Value<'a, T>)>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Text<'a>,
+    T::Value: AsRef<str>,
+    S: Serializer,
+{
+    let nodes: Vec<_> = args
+        .iter()
+        .map(|(name, value)| NamedValueNode {
+            kind: "Argument",
+            name: name.as_ref(),
+            value,
+        })
+        .collect();
+    nodes.serialize(serializer)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord"))
+)]
+pub struct Directive<'a, T: Text<'a>> {
+    #[cfg_attr(
+        feature = "serde_json",
+        serde(
+            serialize_with = "serialize_arguments",
+            deserialize_with = "deserialize_arguments"
+        )
+    )]
+    pub arguments: Vec<(T::Value, Value<'a, T>)>,
+}
+
+/// Wraps a name so it serializes the way graphql-js's `Name` node does:
+/// `{ "kind": "Name", "value": "..." }`.
+pub struct NameKind<'a> {
+    pub value: &'a str,
+}
+
+impl<'a> Serialize for NameKind<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("kind", "Name")?;
+        map.serialize_entry("value", self.value)?;
+        map.end()
+    }
+}
+
+pub fn serialize_name<'a, T, S>(name: &T::Value, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Text<'a>,
+    T::Value: AsRef<str>,
+    S: Serializer,
+{
+    NameKind {
+        value: name.as_ref(),
+    }
+    .serialize(serializer)
+}
+
+pub fn serialize_optional_name<'a, T, S>(
+    name: &Option<T::Value>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Text<'a>,
+    T::Value: AsRef<str>,
+    S: Serializer,
+{
+    match name {
+        Some(name) => serializer.serialize_some(&NameKind {
+            value: name.as_ref(),
+        }),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(feature = "serde_json")]
+struct NameVisitor<V>(PhantomData<V>);
+
+#[cfg(feature = "serde_json")]
+impl<'de, V> Visitor<'de> for NameVisitor<V>
+where
+    V: From<String>,
+{
+    type Value = V;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a graphql-js Name node ({{ kind: \"Name\", value: .. }})"
+        )
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut value = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "kind" => {
+                    map.next_value::<String>()?;
+                }
+                "value" => value = Some(map.next_value::<String>()?),
+                _ => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+        value
+            .map(V::from)
+            .ok_or_else(|| de::Error::missing_field("value"))
+    }
+}
+
+/// Deserializes a graphql-js `Name` node (`{ kind: "Name", value: ... }`)
+/// into a bare `T::Value`, mirroring [`serialize_name`].
+#[cfg(feature = "serde_json")]
+pub fn deserialize_name<'de, D, V>(deserializer: D) -> Result<V, D::Error>
+where
+    D: Deserializer<'de>,
+    V: From<String>,
+{
+    deserializer.deserialize_map(NameVisitor(PhantomData))
+}
+
+/// Deserializes an optional graphql-js `Name` node, mirroring
+/// [`serialize_optional_name`].
+#[cfg(feature = "serde_json")]
+pub fn deserialize_optional_name<'de, D, V>(deserializer: D) -> Result<Option<V>, D::Error>
+where
+    D: Deserializer<'de>,
+    V: From<String>,
+{
+    Option::<NameNodeHelper>::deserialize(deserializer).map(|opt| opt.map(|n| V::from(n.value)))
+}
+
+#[cfg(feature = "serde_json")]
+#[derive(Deserialize)]
+struct NameNodeHelper {
+    value: String,
+}
+
+/// Deserializes the `arguments` field of a [`Directive`] or `Field` from
+/// graphql-js's `Argument` node array, mirroring [`serialize_arguments`]:
+/// `[{ kind: "Argument", name: <Name>, value: <Value> }, ..]`.
+#[cfg(feature = "serde_json")]
+pub fn deserialize_arguments<'de, D, T>(
+    deserializer: D,
+) -> Result<Vec<(T::Value, Value<'static, T>)>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Text<'static>,
+    T::Value: From<String> + Ord,
+{
+    struct ArgumentsVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for ArgumentsVisitor<T>
+    where
+        T: Text<'static>,
+        T::Value: From<String> + Ord,
+    {
+        type Value = Vec<(T::Value, Value<'static, T>)>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "an array of graphql-js Argument nodes")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut arguments = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(argument) = seq.next_element::<ArgumentHelper<T>>()? {
+                arguments.push((argument.name.into(), argument.value));
+            }
+            Ok(arguments)
+        }
+    }
+
+    deserializer.deserialize_seq(ArgumentsVisitor(PhantomData))
+}
+
+#[cfg(feature = "serde_json")]
+struct ArgumentHelper<T: Text<'static>> {
+    name: String,
+    value: Value<'static, T>,
+}
+
+#[cfg(feature = "serde_json")]
+impl<'de, T> Deserialize<'de> for ArgumentHelper<T>
+where
+    T: Text<'static>,
+    T::Value: From<String> + Ord,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Raw<V> {
+            name: NameNodeHelper,
+            value: V,
+        }
+
+        let raw = Raw::<Value<'static, T>>::deserialize(deserializer)?;
+        Ok(ArgumentHelper {
+            name: raw.name.value,
+            value: raw.value,
+        })
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<'de, T> Deserialize<'de> for Value<'static, T>
+where
+    T: Text<'static>,
+    T::Value: From<String> + Ord,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor<T>(PhantomData<T>);
+
+        impl<'de, T> Visitor<'de> for ValueVisitor<T>
+        where
+            T: Text<'static>,
+            T::Value: From<String> + Ord,
+        {
+            type Value = Value<'static, T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a graphql-js value node")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                #[derive(Deserialize)]
+                #[serde(tag = "kind")]
+                enum Raw<T: Text<'static>>
+                where
+                    T::Value: From<String> + Ord,
+                {
+                    Variable {
+                        name: NameNodeHelper,
+                    },
+                    IntValue {
+                        value: String,
+                    },
+                    FloatValue {
+                        value: String,
+                    },
+                    StringValue {
+                        value: String,
+                        #[serde(default)]
+                        block: bool,
+                    },
+                    BooleanValue {
+                        value: bool,
+                    },
+                    NullValue,
+                    EnumValue {
+                        value: String,
+                    },
+                    ListValue {
+                        values: Vec<Value<'static, T>>,
+                    },
+                    ObjectValue {
+                        fields: Vec<ObjectFieldHelper<T>>,
+                    },
+                }
+
+                let raw = Raw::<T>::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(match raw {
+                    Raw::Variable { name } => Value::Variable(name.value.into()),
+                    Raw::IntValue { value } => Value::Int(Number(value)),
+                    Raw::FloatValue { value } => {
+                        Value::Float(value.parse().map_err(de::Error::custom)?)
+                    }
+                    Raw::StringValue { value, block } => Value::String(value, block),
+                    Raw::BooleanValue { value } => Value::Boolean(value),
+                    Raw::NullValue => Value::Null,
+                    Raw::EnumValue { value } => Value::Enum(value.into()),
+                    Raw::ListValue { values } => Value::List(values),
+                    Raw::ObjectValue { fields } => Value::Object(
+                        fields
+                            .into_iter()
+                            .map(|field| (field.name.into(), field.value))
+                            .collect(),
+                    ),
+                })
+            }
+        }
+
+        deserializer.deserialize_map(ValueVisitor(PhantomData))
+    }
+}
+
+#[cfg(feature = "serde_json")]
+struct ObjectFieldHelper<T: Text<'static>> {
+    name: String,
+    value: Value<'static, T>,
+}
+
+#[cfg(feature = "serde_json")]
+impl<'de, T> Deserialize<'de> for ObjectFieldHelper<T>
+where
+    T: Text<'static>,
+    T::Value: From<String> + Ord,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Raw<V> {
+            name: NameNodeHelper,
+            value: V,
+        }
+
+        let raw = Raw::<Value<'static, T>>::deserialize(deserializer)?;
+        Ok(ObjectFieldHelper {
+            name: raw.name.value,
+            value: raw.value,
+        })
+    }
+}
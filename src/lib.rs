@@ -1,26 +1,12 @@
-use serde::{Serialize, Serializer};
-
-pub trait Text<'a>: 'a {
-    type Value: 'a;
-}
-
-pub enum Value<'a, T: Text<'a>> {
-    Object(std::collections::BTreeMap<T::Value, Value<'a, T>>),
-}
-
-pub fn serialize_arguments<'a, T, S>(
-    args: &'a Vec<(T::Value, Value<'a, T>)>,
-    serializer: S,
-) -> Result<S::Ok, S::Error>
-where
-    T: Text<'a>,
-    S: Serializer,
-{
-    unimplemented!()
-}
-
-#[derive(Serialize)]
-pub struct Directive<'a, T: Text<'a>> {
-    #[serde(serialize_with = "serialize_arguments")]
-    pub arguments: Vec<(T::Value, Value<'a, T>)>,
-}
+pub mod common;
+pub mod position;
+pub mod query;
+#[cfg(feature = "typescript")]
+pub mod typescript;
+pub mod visitor;
+
+pub use common::{Text, Value};
+pub use query::Document;
+
+#[cfg(feature = "serde_json")]
+pub use query::parse_query_json;
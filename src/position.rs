@@ -0,0 +1,74 @@
+//! Source code position tracking.
+
+use std::fmt;
+
+#[cfg(feature = "graphql_js_loc")]
+use serde::{Serialize, Serializer};
+
+/// A position in the source text, expressed as a 1-based line/column plus
+/// the byte offset from the start of the document.
+///
+/// Operation, fragment, field, selection-set and variable-definition nodes
+/// carry a `Pos` (or a `span: (Pos, Pos)`) so that parse
+/// errors and downstream tooling can point back at the original document.
+/// `offset` is what lets [`serialize_loc`]/[`serialize_span_loc`] expose
+/// graphql-js-compatible `loc: { start, end }` ranges.
+///
+/// No parser in this crate slice populates `offset` yet, so it is `None`
+/// rather than a type that could be mistaken for a real `0` — callers who
+/// match on it can't accidentally treat "unset" as "start of document".
+/// A future parser can start threading real offsets through by constructing
+/// `Pos` with `Some(..)` without another wire format change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Pos {
+    pub line: usize,
+    pub column: usize,
+    pub offset: Option<usize>,
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A graphql-js-compatible source range: a pair of byte offsets into the
+/// original document. `start`/`end` are `null` rather than `0` when the
+/// underlying [`Pos::offset`] was never populated, so consumers can tell
+/// "no offset available" apart from a real offset at the start of the
+/// document.
+#[cfg(feature = "graphql_js_loc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Loc {
+    pub start: Option<usize>,
+    pub end: Option<usize>,
+}
+
+/// Serializes a single [`Pos`] as a zero-width `loc`.
+///
+/// Used by AST nodes that only record where they start; until the parser
+/// threads an end position through as well, `start` and `end` coincide.
+#[cfg(feature = "graphql_js_loc")]
+pub fn serialize_loc<S>(position: &Pos, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    Loc {
+        start: position.offset,
+        end: position.offset,
+    }
+    .serialize(serializer)
+}
+
+/// Serializes a `(start, end)` span as a `loc`.
+#[cfg(feature = "graphql_js_loc")]
+pub fn serialize_span_loc<S>(span: &(Pos, Pos), serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    Loc {
+        start: span.0.offset,
+        end: span.1.offset,
+    }
+    .serialize(serializer)
+}
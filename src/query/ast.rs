@@ -5,7 +5,13 @@
 //!
 //! [graphql grammar]: http://facebook.github.io/graphql/October2016/#sec-Appendix-Grammar-Summary
 //!
+#[cfg(feature = "serde_json")]
+use serde::de::{self, Deserializer, MapAccess, Visitor};
+#[cfg(feature = "serde_json")]
+use serde::Deserialize;
 use serde::{ser::SerializeMap, Serialize};
+#[cfg(feature = "serde_json")]
+use std::fmt;
 
 pub use crate::common::{Directive, Number, Text, Type, Value};
 use crate::position::Pos;
@@ -13,6 +19,11 @@ use crate::position::Pos;
 /// Root of query data
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"))
+)]
 pub struct Document<'a, T: Text<'a>> {
     pub definitions: Vec<Definition<'a, T>>,
 }
@@ -46,15 +57,58 @@ pub enum Definition<'a, T: Text<'a>> {
     Fragment(FragmentDefinition<'a, T>),
 }
 
+#[cfg(feature = "serde_json")]
+impl<'de, T> Deserialize<'de> for Definition<'static, T>
+where
+    T: Text<'static>,
+    T::Value: From<String> + Ord + AsRef<str>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(
+            tag = "kind",
+            bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>")
+        )]
+        enum Raw<T: Text<'static>>
+        where
+            T::Value: From<String> + Ord + AsRef<str>,
+        {
+            OperationDefinition(OperationDefinition<'static, T>),
+            FragmentDefinition(FragmentDefinition<'static, T>),
+        }
+
+        Ok(match Raw::<T>::deserialize(deserializer)? {
+            Raw::OperationDefinition(op) => Definition::Operation(op),
+            Raw::FragmentDefinition(frag) => Definition::Fragment(frag),
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde_json", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"))
+)]
 pub struct FragmentDefinition<'a, T: Text<'a>> {
-    #[serde(skip)]
+    #[serde(skip_deserializing, default)]
+    #[cfg_attr(
+        feature = "graphql_js_loc",
+        serde(rename = "loc", serialize_with = "crate::position::serialize_loc")
+    )]
+    #[cfg_attr(not(feature = "graphql_js_loc"), serde(skip_serializing))]
     pub position: Pos,
     #[cfg_attr(
         feature = "serde_json",
-        serde(serialize_with = "crate::common::serialize_name")
+        serde(
+            serialize_with = "crate::common::serialize_name",
+            deserialize_with = "crate::common::deserialize_name"
+        )
     )]
     pub name: T::Value,
     pub type_condition: TypeCondition<'a, T>,
@@ -75,15 +129,129 @@ pub enum OperationDefinition<'a, T: Text<'a>> {
     Subscription(Subscription<'a, T>),
 }
 
+#[cfg(feature = "serde_json")]
+impl<'de, T> Deserialize<'de> for OperationDefinition<'static, T>
+where
+    T: Text<'static>,
+    T::Value: From<String> + Ord + AsRef<str>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // The selection-set-only shorthand serializes its `selections`
+        // inline (`{ operation: "selectionSet", selections }`) with no
+        // nested `selectionSet`, while named operations nest theirs, so
+        // the node is read field by field rather than as a plain
+        // internally-tagged enum. A missing or unrecognised `operation`
+        // is read as the shorthand.
+        struct OperationVisitor<T>(std::marker::PhantomData<T>);
+
+        impl<'de, T> Visitor<'de> for OperationVisitor<T>
+        where
+            T: Text<'static>,
+            T::Value: From<String> + Ord + AsRef<str>,
+        {
+            type Value = OperationDefinition<'static, T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a graphql-js OperationDefinition node")
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                #[derive(Deserialize)]
+                #[serde(
+                    rename_all = "camelCase",
+                    bound(
+                        deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"
+                    )
+                )]
+                struct Raw<T: Text<'static>>
+                where
+                    T::Value: From<String> + Ord + AsRef<str>,
+                {
+                    #[serde(default)]
+                    operation: Option<String>,
+                    #[serde(
+                        default,
+                        deserialize_with = "crate::common::deserialize_optional_name"
+                    )]
+                    name: Option<T::Value>,
+                    #[serde(default)]
+                    variable_definitions: Vec<VariableDefinition<'static, T>>,
+                    #[serde(default)]
+                    directives: Vec<Directive<'static, T>>,
+                    #[serde(default)]
+                    selection_set: Option<SelectionSet<'static, T>>,
+                    #[serde(default)]
+                    selections: Option<Vec<Selection<'static, T>>>,
+                }
+
+                let raw = Raw::<T>::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                let selection_set = match (raw.selection_set, raw.selections) {
+                    (Some(selection_set), _) => selection_set,
+                    (None, Some(items)) => SelectionSet {
+                        span: (Pos::default(), Pos::default()),
+                        items,
+                    },
+                    (None, None) => return Err(de::Error::missing_field("selectionSet")),
+                };
+                Ok(match raw.operation.as_deref() {
+                    Some("mutation") => OperationDefinition::Mutation(Mutation {
+                        position: Pos::default(),
+                        name: raw.name,
+                        variable_definitions: raw.variable_definitions,
+                        directives: raw.directives,
+                        selection_set,
+                    }),
+                    Some("subscription") => OperationDefinition::Subscription(Subscription {
+                        position: Pos::default(),
+                        name: raw.name,
+                        variable_definitions: raw.variable_definitions,
+                        directives: raw.directives,
+                        selection_set,
+                    }),
+                    Some("query") => OperationDefinition::Query(Query {
+                        position: Pos::default(),
+                        name: raw.name,
+                        variable_definitions: raw.variable_definitions,
+                        directives: raw.directives,
+                        selection_set,
+                    }),
+                    _ => OperationDefinition::SelectionSet(selection_set),
+                })
+            }
+        }
+
+        deserializer.deserialize_map(OperationVisitor(std::marker::PhantomData))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde_json", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"))
+)]
 pub struct Query<'a, T: Text<'a>> {
-    #[serde(skip)]
+    #[serde(skip_deserializing, default)]
+    #[cfg_attr(
+        feature = "graphql_js_loc",
+        serde(rename = "loc", serialize_with = "crate::position::serialize_loc")
+    )]
+    #[cfg_attr(not(feature = "graphql_js_loc"), serde(skip_serializing))]
     pub position: Pos,
     #[cfg_attr(
         feature = "serde_json",
-        serde(serialize_with = "crate::common::serialize_optional_name")
+        serde(
+            serialize_with = "crate::common::serialize_optional_name",
+            deserialize_with = "crate::common::deserialize_optional_name"
+        )
     )]
     pub name: Option<T::Value>,
     pub variable_definitions: Vec<VariableDefinition<'a, T>>,
@@ -94,12 +262,25 @@ pub struct Query<'a, T: Text<'a>> {
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde_json", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"))
+)]
 pub struct Mutation<'a, T: Text<'a>> {
-    #[serde(skip)]
+    #[serde(skip_deserializing, default)]
+    #[cfg_attr(
+        feature = "graphql_js_loc",
+        serde(rename = "loc", serialize_with = "crate::position::serialize_loc")
+    )]
+    #[cfg_attr(not(feature = "graphql_js_loc"), serde(skip_serializing))]
     pub position: Pos,
     #[cfg_attr(
         feature = "serde_json",
-        serde(serialize_with = "crate::common::serialize_optional_name")
+        serde(
+            serialize_with = "crate::common::serialize_optional_name",
+            deserialize_with = "crate::common::deserialize_optional_name"
+        )
     )]
     pub name: Option<T::Value>,
     pub variable_definitions: Vec<VariableDefinition<'a, T>>,
@@ -110,12 +291,25 @@ pub struct Mutation<'a, T: Text<'a>> {
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde_json", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"))
+)]
 pub struct Subscription<'a, T: Text<'a>> {
-    #[serde(skip)]
+    #[serde(skip_deserializing, default)]
+    #[cfg_attr(
+        feature = "graphql_js_loc",
+        serde(rename = "loc", serialize_with = "crate::position::serialize_loc")
+    )]
+    #[cfg_attr(not(feature = "graphql_js_loc"), serde(skip_serializing))]
     pub position: Pos,
     #[cfg_attr(
         feature = "serde_json",
-        serde(serialize_with = "crate::common::serialize_optional_name")
+        serde(
+            serialize_with = "crate::common::serialize_optional_name",
+            deserialize_with = "crate::common::deserialize_optional_name"
+        )
     )]
     pub name: Option<T::Value>,
     pub variable_definitions: Vec<VariableDefinition<'a, T>>,
@@ -126,22 +320,56 @@ pub struct Subscription<'a, T: Text<'a>> {
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde_json", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"))
+)]
 pub struct SelectionSet<'a, T: Text<'a>> {
-    #[serde(skip)]
+    #[serde(skip_deserializing, default)]
+    #[cfg_attr(
+        feature = "graphql_js_loc",
+        serde(rename = "loc", serialize_with = "crate::position::serialize_span_loc")
+    )]
+    #[cfg_attr(not(feature = "graphql_js_loc"), serde(skip_serializing))]
     pub span: (Pos, Pos),
     #[serde(rename = "selections")]
     pub items: Vec<Selection<'a, T>>,
 }
 
+/// An empty selection set, for leaf fields that graphql-js's `parse` emits
+/// with `selectionSet: undefined` rather than an empty `{ selections: [] }`.
+impl<'a, T: Text<'a>> Default for SelectionSet<'a, T> {
+    fn default() -> Self {
+        SelectionSet {
+            span: (Pos::default(), Pos::default()),
+            items: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde_json", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"))
+)]
 pub struct VariableDefinition<'a, T: Text<'a>> {
-    #[serde(skip)]
+    #[serde(skip_deserializing, default)]
+    #[cfg_attr(
+        feature = "graphql_js_loc",
+        serde(rename = "loc", serialize_with = "crate::position::serialize_loc")
+    )]
+    #[cfg_attr(not(feature = "graphql_js_loc"), serde(skip_serializing))]
     pub position: Pos,
     #[cfg_attr(
         feature = "serde_json",
-        serde(serialize_with = "crate::common::serialize_name")
+        serde(
+            serialize_with = "crate::common::serialize_name",
+            deserialize_with = "crate::common::deserialize_name"
+        )
     )]
     pub name: T::Value,
     pub var_type: Type<'a, T>,
@@ -157,40 +385,107 @@ pub enum Selection<'a, T: Text<'a>> {
     InlineFragment(InlineFragment<'a, T>),
 }
 
+#[cfg(feature = "serde_json")]
+impl<'de, T> Deserialize<'de> for Selection<'static, T>
+where
+    T: Text<'static>,
+    T::Value: From<String> + Ord + AsRef<str>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(
+            tag = "kind",
+            bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>")
+        )]
+        enum Raw<T: Text<'static>>
+        where
+            T::Value: From<String> + Ord + AsRef<str>,
+        {
+            Field(Field<'static, T>),
+            FragmentSpread(FragmentSpread<'static, T>),
+            InlineFragment(InlineFragment<'static, T>),
+        }
+
+        Ok(match Raw::<T>::deserialize(deserializer)? {
+            Raw::Field(field) => Selection::Field(field),
+            Raw::FragmentSpread(spread) => Selection::FragmentSpread(spread),
+            Raw::InlineFragment(fragment) => Selection::InlineFragment(fragment),
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde_json", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"))
+)]
 pub struct Field<'a, T: Text<'a>> {
-    #[serde(skip)]
+    #[serde(skip_deserializing, default)]
+    #[cfg_attr(
+        feature = "graphql_js_loc",
+        serde(rename = "loc", serialize_with = "crate::position::serialize_loc")
+    )]
+    #[cfg_attr(not(feature = "graphql_js_loc"), serde(skip_serializing))]
     pub position: Pos,
     #[cfg_attr(
         feature = "serde_json",
-        serde(serialize_with = "crate::common::serialize_optional_name")
+        serde(
+            serialize_with = "crate::common::serialize_optional_name",
+            deserialize_with = "crate::common::deserialize_optional_name",
+            default
+        )
     )]
     pub alias: Option<T::Value>,
     #[cfg_attr(
         feature = "serde_json",
-        serde(serialize_with = "crate::common::serialize_name")
+        serde(
+            serialize_with = "crate::common::serialize_name",
+            deserialize_with = "crate::common::deserialize_name"
+        )
     )]
     pub name: T::Value,
     #[cfg_attr(
         feature = "serde_json",
-        serde(serialize_with = "crate::common::serialize_arguments")
+        serde(
+            serialize_with = "crate::common::serialize_arguments",
+            deserialize_with = "crate::common::deserialize_arguments",
+            default
+        )
     )]
     pub arguments: Vec<(T::Value, Value<'a, T>)>,
     pub directives: Vec<Directive<'a, T>>,
+    #[cfg_attr(feature = "serde_json", serde(default))]
     pub selection_set: SelectionSet<'a, T>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde_json", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"))
+)]
 pub struct FragmentSpread<'a, T: Text<'a>> {
-    #[serde(skip)]
+    #[serde(skip_deserializing, default)]
+    #[cfg_attr(
+        feature = "graphql_js_loc",
+        serde(rename = "loc", serialize_with = "crate::position::serialize_loc")
+    )]
+    #[cfg_attr(not(feature = "graphql_js_loc"), serde(skip_serializing))]
     pub position: Pos,
     #[cfg_attr(
         feature = "serde_json",
-        serde(serialize_with = "crate::common::serialize_name")
+        serde(
+            serialize_with = "crate::common::serialize_name",
+            deserialize_with = "crate::common::deserialize_name"
+        )
     )]
     pub fragment_name: T::Value,
     pub directives: Vec<Directive<'a, T>>,
@@ -223,11 +518,78 @@ impl<'a, T: Text<'a>> Serialize for TypeCondition<'a, T> {
     }
 }
 
+#[cfg(feature = "serde_json")]
+impl<'de, T> Deserialize<'de> for TypeCondition<'static, T>
+where
+    T: Text<'static>,
+    T::Value: From<String>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TypeConditionVisitor<T>(std::marker::PhantomData<T>);
+
+        impl<'de, T> Visitor<'de> for TypeConditionVisitor<T>
+        where
+            T: Text<'static>,
+            T::Value: From<String>,
+        {
+            type Value = TypeCondition<'static, T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    f,
+                    "a graphql-js TypeCondition ({{ kind: \"NamedType\", value: <Name> }})"
+                )
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                #[derive(Deserialize)]
+                struct NameNode {
+                    value: String,
+                }
+
+                let mut value = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "kind" => {
+                            map.next_value::<String>()?;
+                        }
+                        "value" => value = Some(map.next_value::<NameNode>()?.value),
+                        _ => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                value
+                    .map(|value| TypeCondition::On(T::Value::from(value)))
+                    .ok_or_else(|| de::Error::missing_field("value"))
+            }
+        }
+
+        deserializer.deserialize_map(TypeConditionVisitor(std::marker::PhantomData))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde_json", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde_json", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde_json",
+    derive(serde::Deserialize),
+    serde(bound(deserialize = "T: Text<'static>, T::Value: From<String> + Ord + AsRef<str>"))
+)]
 pub struct InlineFragment<'a, T: Text<'a>> {
-    #[serde(skip)]
+    #[serde(skip_deserializing, default)]
+    #[cfg_attr(
+        feature = "graphql_js_loc",
+        serde(rename = "loc", serialize_with = "crate::position::serialize_loc")
+    )]
+    #[cfg_attr(not(feature = "graphql_js_loc"), serde(skip_serializing))]
     pub position: Pos,
     pub type_condition: Option<TypeCondition<'a, T>>,
     pub directives: Vec<Directive<'a, T>>,
@@ -0,0 +1,18 @@
+//! The GraphQL query language: its AST and JSON interop.
+
+mod ast;
+
+pub use ast::*;
+
+/// Reads a graphql-js-style JSON AST (as produced by `serde_json::to_value`
+/// on a [`Document`], or emitted directly by graphql-js's `parse`) back
+/// into a [`Document`].
+///
+/// This is the JSON-AST counterpart to the text-based query parser: where
+/// that parser turns GraphQL source into a `Document`, this turns JSON
+/// into the same `Document`, letting this crate interoperate with
+/// JS-side tooling that already holds a parsed AST.
+#[cfg(feature = "serde_json")]
+pub fn parse_query_json(json: &str) -> serde_json::Result<Document<'static, String>> {
+    serde_json::from_str(json)
+}
@@ -0,0 +1,141 @@
+//! Hand-written TypeScript declarations for the serialized query AST.
+//!
+//! A `tsify`/`ts-rs`-style derive macro mirrors a type's *Rust* field
+//! layout, but our JSON output intentionally diverges from it: the
+//! internally tagged `kind`/`operation` unions, the `{ kind: "Name",
+//! value }` wrappers, the `selections` rename, and the value-node union
+//! all come from serde attributes a derive macro doesn't see. So instead
+//! of deriving from the Rust types, this module is the source of truth
+//! for the `.d.ts` text, written against the same shapes the serializers
+//! in [`crate::common`] and [`crate::query`] produce. `tests/typescript_codegen.rs`
+//! asserts [`generate`]'s output against a committed golden file
+//! (`tests/queries/ast.d.ts`) so the two can't silently drift apart.
+
+/// Renders the full `.d.ts` text for the serialized query AST.
+pub fn generate() -> String {
+    [
+        NAME,
+        TYPE,
+        VALUE,
+        ARGUMENT,
+        DIRECTIVE,
+        TYPE_CONDITION,
+        VARIABLE_DEFINITION,
+        FIELD,
+        FRAGMENT_SPREAD,
+        INLINE_FRAGMENT,
+        SELECTION,
+        SELECTION_SET,
+        FRAGMENT_DEFINITION,
+        OPERATION_DEFINITION,
+        DEFINITION,
+        DOCUMENT,
+    ]
+    .join("\n\n")
+        + "\n"
+}
+
+const NAME: &str = r#"export interface Name {
+  kind: "Name";
+  value: string;
+}"#;
+
+const TYPE: &str = r#"export type Type =
+  | { kind: "NamedType"; name: Name }
+  | { kind: "ListType"; type: Type }
+  | { kind: "NonNullType"; type: Type };"#;
+
+const VALUE: &str = r#"export type Value =
+  | { kind: "Variable"; name: Name }
+  | { kind: "IntValue"; value: string }
+  | { kind: "FloatValue"; value: string }
+  | { kind: "StringValue"; value: string; block: boolean }
+  | { kind: "BooleanValue"; value: boolean }
+  | { kind: "NullValue" }
+  | { kind: "EnumValue"; value: string }
+  | { kind: "ListValue"; values: Value[] }
+  | { kind: "ObjectValue"; fields: ObjectField[] };
+
+export interface ObjectField {
+  kind: "ObjectField";
+  name: Name;
+  value: Value;
+}"#;
+
+const ARGUMENT: &str = r#"export interface Argument {
+  kind: "Argument";
+  name: Name;
+  value: Value;
+}"#;
+
+const DIRECTIVE: &str = r#"export interface Directive {
+  arguments: Argument[];
+}"#;
+
+const TYPE_CONDITION: &str = r#"export interface TypeCondition {
+  kind: "NamedType";
+  value: Name;
+}"#;
+
+const VARIABLE_DEFINITION: &str = r#"export interface VariableDefinition {
+  name: Name;
+  varType: Type;
+  defaultValue: Value | null;
+}"#;
+
+const FIELD: &str = r#"export interface Field {
+  kind: "Field";
+  alias: Name | null;
+  name: Name;
+  arguments: Argument[];
+  directives: Directive[];
+  selectionSet: SelectionSet;
+}"#;
+
+const FRAGMENT_SPREAD: &str = r#"export interface FragmentSpread {
+  kind: "FragmentSpread";
+  fragmentName: Name;
+  directives: Directive[];
+}"#;
+
+const INLINE_FRAGMENT: &str = r#"export interface InlineFragment {
+  kind: "InlineFragment";
+  typeCondition: TypeCondition | null;
+  directives: Directive[];
+  selectionSet: SelectionSet;
+}"#;
+
+const SELECTION: &str = r#"export type Selection = Field | FragmentSpread | InlineFragment;"#;
+
+const SELECTION_SET: &str = r#"export interface SelectionSet {
+  selections: Selection[];
+}"#;
+
+const FRAGMENT_DEFINITION: &str = r#"export interface FragmentDefinition {
+  kind: "FragmentDefinition";
+  name: Name;
+  typeCondition: TypeCondition;
+  directives: Directive[];
+  selectionSet: SelectionSet;
+}"#;
+
+const OPERATION_DEFINITION: &str = r#"export type OperationDefinition =
+  | ({ operation: "selectionSet" } & SelectionSet)
+  | ({ operation: "query" } & OperationShape)
+  | ({ operation: "mutation" } & OperationShape)
+  | ({ operation: "subscription" } & OperationShape);
+
+interface OperationShape {
+  name: Name | null;
+  variableDefinitions: VariableDefinition[];
+  directives: Directive[];
+  selectionSet: SelectionSet;
+}"#;
+
+const DEFINITION: &str = r#"export type Definition =
+  | ({ kind: "OperationDefinition" } & OperationDefinition)
+  | FragmentDefinition;"#;
+
+const DOCUMENT: &str = r#"export interface Document {
+  definitions: Definition[];
+}"#;
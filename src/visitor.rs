@@ -0,0 +1,467 @@
+//! A visitor/traversal subsystem over the query AST.
+//!
+//! [`Visitor`] (and its mutable counterpart, [`VisitorMut`]) let callers
+//! walk or rewrite a [`Document`] without hand-matching every `Selection`
+//! and `Value` variant themselves. Every method is enter/leave paired and
+//! default-implemented as a no-op, so a linting pass, a depth limiter, or
+//! a fragment-usage collector only overrides the handful of nodes it
+//! cares about. The `walk_*` functions do the actual recursion and are
+//! what most callers should invoke (e.g. [`walk_document`]); the
+//! `visit_*`/`leave_*` methods are the hooks `walk_*` calls along the way.
+
+use crate::common::{Directive, Text, Value};
+use crate::query::{
+    Definition, Document, Field, FragmentDefinition, FragmentSpread, InlineFragment,
+    OperationDefinition, Selection, SelectionSet, VariableDefinition,
+};
+
+/// Read-only traversal of a query AST.
+pub trait Visitor<'a, T: Text<'a>> {
+    fn visit_operation(&mut self, _operation: &OperationDefinition<'a, T>) {}
+    fn leave_operation(&mut self, _operation: &OperationDefinition<'a, T>) {}
+
+    fn visit_field(&mut self, _field: &Field<'a, T>) {}
+    fn leave_field(&mut self, _field: &Field<'a, T>) {}
+
+    fn visit_fragment_spread(&mut self, _spread: &FragmentSpread<'a, T>) {}
+    fn leave_fragment_spread(&mut self, _spread: &FragmentSpread<'a, T>) {}
+
+    fn visit_inline_fragment(&mut self, _fragment: &InlineFragment<'a, T>) {}
+    fn leave_inline_fragment(&mut self, _fragment: &InlineFragment<'a, T>) {}
+
+    fn visit_variable_definition(&mut self, _definition: &VariableDefinition<'a, T>) {}
+    fn leave_variable_definition(&mut self, _definition: &VariableDefinition<'a, T>) {}
+
+    fn visit_directive(&mut self, _directive: &Directive<'a, T>) {}
+    fn leave_directive(&mut self, _directive: &Directive<'a, T>) {}
+
+    fn visit_value(&mut self, _value: &Value<'a, T>) {}
+    fn leave_value(&mut self, _value: &Value<'a, T>) {}
+}
+
+/// Walks every definition in a [`Document`].
+pub fn walk_document<'a, T, V>(visitor: &mut V, document: &Document<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    for definition in &document.definitions {
+        walk_definition(visitor, definition);
+    }
+}
+
+/// Walks a single [`Definition`], dispatching to [`walk_operation`] or
+/// recursing into a fragment's selection set.
+pub fn walk_definition<'a, T, V>(visitor: &mut V, definition: &Definition<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    match definition {
+        Definition::Operation(operation) => walk_operation(visitor, operation),
+        Definition::Fragment(fragment) => walk_fragment_definition(visitor, fragment),
+    }
+}
+
+/// Walks a [`FragmentDefinition`]'s directives and selection set.
+pub fn walk_fragment_definition<'a, T, V>(visitor: &mut V, fragment: &FragmentDefinition<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    for directive in &fragment.directives {
+        walk_directive(visitor, directive);
+    }
+    walk_selection_set(visitor, &fragment.selection_set);
+}
+
+/// Walks an [`OperationDefinition`]: its variable definitions, directives,
+/// and selection set (a bare `{ ... }` shorthand has only the latter).
+pub fn walk_operation<'a, T, V>(visitor: &mut V, operation: &OperationDefinition<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    visitor.visit_operation(operation);
+    let selection_set = match operation {
+        OperationDefinition::SelectionSet(selection_set) => selection_set,
+        OperationDefinition::Query(query) => {
+            for variable_definition in &query.variable_definitions {
+                walk_variable_definition(visitor, variable_definition);
+            }
+            for directive in &query.directives {
+                walk_directive(visitor, directive);
+            }
+            &query.selection_set
+        }
+        OperationDefinition::Mutation(mutation) => {
+            for variable_definition in &mutation.variable_definitions {
+                walk_variable_definition(visitor, variable_definition);
+            }
+            for directive in &mutation.directives {
+                walk_directive(visitor, directive);
+            }
+            &mutation.selection_set
+        }
+        OperationDefinition::Subscription(subscription) => {
+            for variable_definition in &subscription.variable_definitions {
+                walk_variable_definition(visitor, variable_definition);
+            }
+            for directive in &subscription.directives {
+                walk_directive(visitor, directive);
+            }
+            &subscription.selection_set
+        }
+    };
+    walk_selection_set(visitor, selection_set);
+    visitor.leave_operation(operation);
+}
+
+/// Walks every [`Selection`] in a [`SelectionSet`].
+pub fn walk_selection_set<'a, T, V>(visitor: &mut V, selection_set: &SelectionSet<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    for selection in &selection_set.items {
+        walk_selection(visitor, selection);
+    }
+}
+
+/// Dispatches a [`Selection`] to [`walk_field`], [`walk_fragment_spread`],
+/// or [`walk_inline_fragment`].
+pub fn walk_selection<'a, T, V>(visitor: &mut V, selection: &Selection<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    match selection {
+        Selection::Field(field) => walk_field(visitor, field),
+        Selection::FragmentSpread(spread) => walk_fragment_spread(visitor, spread),
+        Selection::InlineFragment(fragment) => walk_inline_fragment(visitor, fragment),
+    }
+}
+
+/// Walks a [`Field`]'s arguments, directives, and nested selection set.
+pub fn walk_field<'a, T, V>(visitor: &mut V, field: &Field<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    visitor.visit_field(field);
+    for (_name, value) in &field.arguments {
+        walk_value(visitor, value);
+    }
+    for directive in &field.directives {
+        walk_directive(visitor, directive);
+    }
+    walk_selection_set(visitor, &field.selection_set);
+    visitor.leave_field(field);
+}
+
+/// Walks a [`FragmentSpread`]'s directives. This does *not* follow the
+/// spread into the fragment it names (callers collecting fragment usage
+/// want the name itself, not a recursive walk, and resolving the spread
+/// requires the rest of the document anyway).
+pub fn walk_fragment_spread<'a, T, V>(visitor: &mut V, spread: &FragmentSpread<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    visitor.visit_fragment_spread(spread);
+    for directive in &spread.directives {
+        walk_directive(visitor, directive);
+    }
+    visitor.leave_fragment_spread(spread);
+}
+
+/// Walks an [`InlineFragment`]'s directives and selection set.
+pub fn walk_inline_fragment<'a, T, V>(visitor: &mut V, fragment: &InlineFragment<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    visitor.visit_inline_fragment(fragment);
+    for directive in &fragment.directives {
+        walk_directive(visitor, directive);
+    }
+    walk_selection_set(visitor, &fragment.selection_set);
+    visitor.leave_inline_fragment(fragment);
+}
+
+/// Walks a [`VariableDefinition`]'s default value, if any.
+pub fn walk_variable_definition<'a, T, V>(visitor: &mut V, definition: &VariableDefinition<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    visitor.visit_variable_definition(definition);
+    if let Some(default_value) = &definition.default_value {
+        walk_value(visitor, default_value);
+    }
+    visitor.leave_variable_definition(definition);
+}
+
+/// Walks a [`Directive`]'s arguments.
+pub fn walk_directive<'a, T, V>(visitor: &mut V, directive: &Directive<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    visitor.visit_directive(directive);
+    for (_name, value) in &directive.arguments {
+        walk_value(visitor, value);
+    }
+    visitor.leave_directive(directive);
+}
+
+/// Walks a [`Value`], recursing into `List` items and `Object` fields.
+pub fn walk_value<'a, T, V>(visitor: &mut V, value: &Value<'a, T>)
+where
+    T: Text<'a>,
+    V: Visitor<'a, T>,
+{
+    visitor.visit_value(value);
+    match value {
+        Value::List(items) => {
+            for item in items {
+                walk_value(visitor, item);
+            }
+        }
+        Value::Object(fields) => {
+            for field_value in fields.values() {
+                walk_value(visitor, field_value);
+            }
+        }
+        _ => {}
+    }
+    visitor.leave_value(value);
+}
+
+/// Rewriting traversal of a query AST.
+///
+/// Mirrors [`Visitor`], but every hook takes `&mut` so a pass can rename
+/// fields, strip directives, or otherwise transform the tree in place.
+pub trait VisitorMut<'a, T: Text<'a>> {
+    fn visit_operation(&mut self, _operation: &mut OperationDefinition<'a, T>) {}
+    fn leave_operation(&mut self, _operation: &mut OperationDefinition<'a, T>) {}
+
+    fn visit_field(&mut self, _field: &mut Field<'a, T>) {}
+    fn leave_field(&mut self, _field: &mut Field<'a, T>) {}
+
+    fn visit_fragment_spread(&mut self, _spread: &mut FragmentSpread<'a, T>) {}
+    fn leave_fragment_spread(&mut self, _spread: &mut FragmentSpread<'a, T>) {}
+
+    fn visit_inline_fragment(&mut self, _fragment: &mut InlineFragment<'a, T>) {}
+    fn leave_inline_fragment(&mut self, _fragment: &mut InlineFragment<'a, T>) {}
+
+    fn visit_variable_definition(&mut self, _definition: &mut VariableDefinition<'a, T>) {}
+    fn leave_variable_definition(&mut self, _definition: &mut VariableDefinition<'a, T>) {}
+
+    fn visit_directive(&mut self, _directive: &mut Directive<'a, T>) {}
+    fn leave_directive(&mut self, _directive: &mut Directive<'a, T>) {}
+
+    fn visit_value(&mut self, _value: &mut Value<'a, T>) {}
+    fn leave_value(&mut self, _value: &mut Value<'a, T>) {}
+}
+
+/// Walks (and can rewrite) every definition in a [`Document`].
+pub fn walk_document_mut<'a, T, V>(visitor: &mut V, document: &mut Document<'a, T>)
+where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    for definition in &mut document.definitions {
+        walk_definition_mut(visitor, definition);
+    }
+}
+
+/// Walks (and can rewrite) a single [`Definition`].
+pub fn walk_definition_mut<'a, T, V>(visitor: &mut V, definition: &mut Definition<'a, T>)
+where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    match definition {
+        Definition::Operation(operation) => walk_operation_mut(visitor, operation),
+        Definition::Fragment(fragment) => walk_fragment_definition_mut(visitor, fragment),
+    }
+}
+
+/// Walks (and can rewrite) a [`FragmentDefinition`]'s directives and
+/// selection set.
+pub fn walk_fragment_definition_mut<'a, T, V>(
+    visitor: &mut V,
+    fragment: &mut FragmentDefinition<'a, T>,
+) where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    for directive in &mut fragment.directives {
+        walk_directive_mut(visitor, directive);
+    }
+    walk_selection_set_mut(visitor, &mut fragment.selection_set);
+}
+
+/// Walks (and can rewrite) an [`OperationDefinition`].
+pub fn walk_operation_mut<'a, T, V>(visitor: &mut V, operation: &mut OperationDefinition<'a, T>)
+where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    visitor.visit_operation(operation);
+    let selection_set = match operation {
+        OperationDefinition::SelectionSet(selection_set) => selection_set,
+        OperationDefinition::Query(query) => {
+            for variable_definition in &mut query.variable_definitions {
+                walk_variable_definition_mut(visitor, variable_definition);
+            }
+            for directive in &mut query.directives {
+                walk_directive_mut(visitor, directive);
+            }
+            &mut query.selection_set
+        }
+        OperationDefinition::Mutation(mutation) => {
+            for variable_definition in &mut mutation.variable_definitions {
+                walk_variable_definition_mut(visitor, variable_definition);
+            }
+            for directive in &mut mutation.directives {
+                walk_directive_mut(visitor, directive);
+            }
+            &mut mutation.selection_set
+        }
+        OperationDefinition::Subscription(subscription) => {
+            for variable_definition in &mut subscription.variable_definitions {
+                walk_variable_definition_mut(visitor, variable_definition);
+            }
+            for directive in &mut subscription.directives {
+                walk_directive_mut(visitor, directive);
+            }
+            &mut subscription.selection_set
+        }
+    };
+    walk_selection_set_mut(visitor, selection_set);
+    visitor.leave_operation(operation);
+}
+
+/// Walks (and can rewrite) every [`Selection`] in a [`SelectionSet`].
+pub fn walk_selection_set_mut<'a, T, V>(visitor: &mut V, selection_set: &mut SelectionSet<'a, T>)
+where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    for selection in &mut selection_set.items {
+        walk_selection_mut(visitor, selection);
+    }
+}
+
+/// Dispatches a [`Selection`] to its matching `walk_*_mut` function.
+pub fn walk_selection_mut<'a, T, V>(visitor: &mut V, selection: &mut Selection<'a, T>)
+where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    match selection {
+        Selection::Field(field) => walk_field_mut(visitor, field),
+        Selection::FragmentSpread(spread) => walk_fragment_spread_mut(visitor, spread),
+        Selection::InlineFragment(fragment) => walk_inline_fragment_mut(visitor, fragment),
+    }
+}
+
+/// Walks (and can rewrite) a [`Field`]'s arguments, directives, and
+/// nested selection set.
+pub fn walk_field_mut<'a, T, V>(visitor: &mut V, field: &mut Field<'a, T>)
+where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    visitor.visit_field(field);
+    for (_name, value) in &mut field.arguments {
+        walk_value_mut(visitor, value);
+    }
+    for directive in &mut field.directives {
+        walk_directive_mut(visitor, directive);
+    }
+    walk_selection_set_mut(visitor, &mut field.selection_set);
+    visitor.leave_field(field);
+}
+
+/// Walks (and can rewrite) a [`FragmentSpread`]'s directives.
+pub fn walk_fragment_spread_mut<'a, T, V>(visitor: &mut V, spread: &mut FragmentSpread<'a, T>)
+where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    visitor.visit_fragment_spread(spread);
+    for directive in &mut spread.directives {
+        walk_directive_mut(visitor, directive);
+    }
+    visitor.leave_fragment_spread(spread);
+}
+
+/// Walks (and can rewrite) an [`InlineFragment`]'s directives and
+/// selection set.
+pub fn walk_inline_fragment_mut<'a, T, V>(visitor: &mut V, fragment: &mut InlineFragment<'a, T>)
+where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    visitor.visit_inline_fragment(fragment);
+    for directive in &mut fragment.directives {
+        walk_directive_mut(visitor, directive);
+    }
+    walk_selection_set_mut(visitor, &mut fragment.selection_set);
+    visitor.leave_inline_fragment(fragment);
+}
+
+/// Walks (and can rewrite) a [`VariableDefinition`]'s default value.
+pub fn walk_variable_definition_mut<'a, T, V>(
+    visitor: &mut V,
+    definition: &mut VariableDefinition<'a, T>,
+) where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    visitor.visit_variable_definition(definition);
+    if let Some(default_value) = &mut definition.default_value {
+        walk_value_mut(visitor, default_value);
+    }
+    visitor.leave_variable_definition(definition);
+}
+
+/// Walks (and can rewrite) a [`Directive`]'s arguments.
+pub fn walk_directive_mut<'a, T, V>(visitor: &mut V, directive: &mut Directive<'a, T>)
+where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    visitor.visit_directive(directive);
+    for (_name, value) in &mut directive.arguments {
+        walk_value_mut(visitor, value);
+    }
+    visitor.leave_directive(directive);
+}
+
+/// Walks (and can rewrite) a [`Value`], recursing into `List` items and
+/// `Object` fields.
+pub fn walk_value_mut<'a, T, V>(visitor: &mut V, value: &mut Value<'a, T>)
+where
+    T: Text<'a>,
+    V: VisitorMut<'a, T>,
+{
+    visitor.visit_value(value);
+    match value {
+        Value::List(items) => {
+            for item in items {
+                walk_value_mut(visitor, item);
+            }
+        }
+        Value::Object(fields) => {
+            for field_value in fields.values_mut() {
+                walk_value_mut(visitor, field_value);
+            }
+        }
+        _ => {}
+    }
+    visitor.leave_value(value);
+}
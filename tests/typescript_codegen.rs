@@ -0,0 +1,9 @@
+#![cfg(feature = "typescript")]
+
+const EXPECTED: &str = include_str!("queries/ast.d.ts");
+
+#[test]
+fn ast_declarations() {
+    let dts = graphql_parser::typescript::generate();
+    assert_eq!(dts, EXPECTED);
+}